@@ -1,14 +1,53 @@
+use std::io::Read;
+
 pub struct DataProvider {
-    reader: Box<dyn std::io::Read>,
+    reader: Source,
     buffer: [u8; 512],
     data_pointer: usize,
     data_available: usize,
+    bytes_loaded: u64,
+    endianness: Endianness,
+}
+
+/// A reader that is also seekable, so `DataProvider::seek` can rewind it.
+pub trait ReadSeek: std::io::Read + std::io::Seek {}
+impl<T: std::io::Read + std::io::Seek> ReadSeek for T {}
+
+enum Source {
+    Read(Box<dyn std::io::Read>),
+    Seek(Box<dyn ReadSeek>),
+}
+
+impl std::io::Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Source::Read(reader) => reader.read(buf),
+            Source::Seek(reader) => reader.read(buf),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    None,
+    Gzip,
+    Zlib,
+    Brotli,
+    Zstd,
 }
 
 #[derive(Debug)]
 pub enum DataError {
     EndOfStream,
     Utf8Error,
+    VarintOverflow,
+    NotSeekable,
     IOError(std::io::Error),
 }
 
@@ -18,6 +57,8 @@ impl PartialEq for DataError {
             (DataError::IOError(_e1), DataError::IOError(_e2)) => true, // Assume error is the same if the type matches
             (DataError::EndOfStream, DataError::EndOfStream) => true,
             (DataError::Utf8Error, DataError::Utf8Error) => true,
+            (DataError::VarintOverflow, DataError::VarintOverflow) => true,
+            (DataError::NotSeekable, DataError::NotSeekable) => true,
             _ => false,
         }
     }
@@ -26,11 +67,130 @@ impl PartialEq for DataError {
 impl DataProvider {
     pub fn new(reader: Box<dyn std::io::Read>) -> DataProvider {
         DataProvider {
-            reader,
+            reader: Source::Read(reader),
+            buffer: [0; 512],
+            data_pointer: 0,
+            data_available: 0,
+            bytes_loaded: 0,
+            endianness: Endianness::Little,
+        }
+    }
+
+    pub fn with_endianness(reader: Box<dyn std::io::Read>, endianness: Endianness) -> DataProvider {
+        DataProvider {
+            reader: Source::Read(reader),
+            buffer: [0; 512],
+            data_pointer: 0,
+            data_available: 0,
+            bytes_loaded: 0,
+            endianness,
+        }
+    }
+
+    /// Builds a `DataProvider` over a seekable source, enabling `seek` and random access
+    /// into indexed trace files.
+    pub fn new_seekable(reader: Box<dyn ReadSeek>) -> DataProvider {
+        DataProvider {
+            reader: Source::Seek(reader),
             buffer: [0; 512],
             data_pointer: 0,
             data_available: 0,
+            bytes_loaded: 0,
+            endianness: Endianness::Little,
+        }
+    }
+
+    /// Returns the absolute number of bytes consumed so far, useful for pointing error
+    /// messages at the exact offset of a malformed event.
+    pub fn position(&self) -> u64 {
+        self.bytes_loaded - (self.data_available - self.data_pointer) as u64
+    }
+
+    /// Seeks the underlying reader and discards the in-memory buffer, so the next read
+    /// reflects the new position. Only available when constructed via `new_seekable`.
+    ///
+    /// The inner reader's raw cursor sits at `bytes_loaded`, ahead of our logical
+    /// `position()` by whatever is buffered but unread, so `SeekFrom::Current` is resolved
+    /// against `position()` ourselves and issued to the inner reader as an absolute
+    /// `SeekFrom::Start` rather than passed through directly.
+    pub fn seek(&mut self, pos: std::io::SeekFrom) -> Result<u64, DataError> {
+        let resolved = match pos {
+            std::io::SeekFrom::Current(delta) => {
+                let target = self.position() as i64 + delta;
+                if target < 0 {
+                    return Err(DataError::IOError(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "invalid seek to a negative position",
+                    )));
+                }
+                std::io::SeekFrom::Start(target as u64)
+            }
+            other => other,
+        };
+
+        match &mut self.reader {
+            Source::Seek(reader) => {
+                let new_pos = reader.seek(resolved).map_err(DataError::IOError)?;
+                self.data_pointer = 0;
+                self.data_available = 0;
+                self.bytes_loaded = new_pos;
+                Ok(new_pos)
+            }
+            Source::Read(_) => Err(DataError::NotSeekable),
+        }
+    }
+
+    pub fn with_encoding(
+        reader: Box<dyn std::io::Read>,
+        encoding: Encoding,
+    ) -> Result<DataProvider, DataError> {
+        let reader: Box<dyn std::io::Read> = match encoding {
+            Encoding::None => reader,
+            Encoding::Gzip => Box::new(flate2::read::GzDecoder::new(reader)),
+            Encoding::Zlib => Box::new(flate2::read::ZlibDecoder::new(reader)),
+            Encoding::Brotli => Box::new(brotli::Decompressor::new(reader, 4096)),
+            Encoding::Zstd => {
+                Box::new(zstd::stream::Decoder::new(reader).map_err(DataError::IOError)?)
+            }
+        };
+
+        Ok(DataProvider::new(reader))
+    }
+
+    /// Peeks the first few bytes of `reader` to guess its compression from well-known
+    /// magic numbers, then builds a `DataProvider` that transparently decompresses it.
+    pub fn with_sniffed_encoding(
+        mut reader: Box<dyn std::io::Read>,
+    ) -> Result<DataProvider, DataError> {
+        let mut magic = [0u8; 4];
+        let read = Self::fill_as_much_as_possible(&mut reader, &mut magic)?;
+
+        let encoding = match &magic[..read] {
+            [0x1f, 0x8b, ..] => Encoding::Gzip,
+            [0x28, 0xb5, 0x2f, 0xfd] => Encoding::Zstd,
+            _ => Encoding::None,
+        };
+
+        let prefixed: Box<dyn std::io::Read> =
+            Box::new(std::io::Cursor::new(magic[..read].to_vec()).chain(reader));
+
+        Self::with_encoding(prefixed, encoding)
+    }
+
+    fn fill_as_much_as_possible(
+        reader: &mut Box<dyn std::io::Read>,
+        buf: &mut [u8],
+    ) -> Result<usize, DataError> {
+        let mut total = 0;
+        while total < buf.len() {
+            match reader.read(&mut buf[total..]) {
+                Ok(0) => break,
+                Ok(n) => total += n,
+                Err(err) => return Err(DataError::IOError(err)),
+            }
         }
+
+        Ok(total)
     }
 
     fn get_next_byte(&mut self) -> Result<u8, DataError> {
@@ -50,18 +210,89 @@ impl DataProvider {
         data
     }
 
+    pub fn peek_byte(&mut self) -> Result<u8, DataError> {
+        if self.data_pointer == self.data_available {
+            match self.load_data() {
+                Err(err) => return Err(DataError::IOError(err)),
+                Ok(_) => {
+                    if self.data_available == 0 {
+                        return Err(DataError::EndOfStream);
+                    }
+                }
+            }
+        }
+
+        Ok(self.buffer[self.data_pointer])
+    }
+
+    pub fn peek_bytes(&mut self, n: usize) -> Result<&[u8], DataError> {
+        if self.data_available - self.data_pointer < n {
+            self.compact_and_refill(n)?;
+        }
+
+        Ok(&self.buffer[self.data_pointer..self.data_pointer + n])
+    }
+
+    fn compact_and_refill(&mut self, n: usize) -> Result<(), DataError> {
+        if n > self.buffer.len() {
+            return Err(DataError::EndOfStream);
+        }
+
+        let remaining = self.data_available - self.data_pointer;
+        self.buffer.copy_within(self.data_pointer..self.data_available, 0);
+        self.data_pointer = 0;
+        self.data_available = remaining;
+
+        while self.data_available < n {
+            match self.reader.read(&mut self.buffer[self.data_available..]) {
+                Ok(0) => return Err(DataError::EndOfStream),
+                Ok(size) => {
+                    self.data_available += size;
+                    self.bytes_loaded += size as u64;
+                }
+                Err(err) => return Err(DataError::IOError(err)),
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), DataError> {
-        // TODO do it more efficiently by copying a whole slice
-        for b in buffer {
-            *b = match self.get_next_byte() {
-                Ok(value) => value,
-                Err(err) => return Err(err),
+        let mut written = 0;
+        while written < buffer.len() {
+            match self.read_once(&mut buffer[written..]) {
+                Ok(0) => return Err(DataError::EndOfStream),
+                Ok(size) => written += size,
+                Err(err) => return Err(DataError::IOError(err)),
             }
         }
 
         Ok(())
     }
 
+    /// Copies at most one buffer's worth of already-loaded (or freshly loaded) data into
+    /// `buf`, returning `Ok(0)` on end of stream rather than erroring, so it can back both
+    /// `read_bytes` and `std::io::Read::read`.
+    fn read_once(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.data_pointer == self.data_available {
+            self.load_data()?;
+            if self.data_available == 0 {
+                return Ok(0);
+            }
+        }
+
+        let available = self.data_available - self.data_pointer;
+        let chunk = std::cmp::min(buf.len(), available);
+        buf[..chunk].copy_from_slice(&self.buffer[self.data_pointer..self.data_pointer + chunk]);
+        self.data_pointer += chunk;
+
+        Ok(chunk)
+    }
+
     pub fn read_string(&mut self) -> Result<String, DataError> {
         let mut data = std::vec::Vec::new();
         loop {
@@ -78,11 +309,127 @@ impl DataProvider {
         }
     }
 
+    /// Reads exactly `len` bytes, growing the output only as bytes actually arrive so an
+    /// untrusted (e.g. wire-supplied) `len` can't force a single huge upfront allocation
+    /// before the stream is known to hold that much data.
+    pub fn read_bytes_vec(&mut self, len: usize) -> Result<Vec<u8>, DataError> {
+        let mut data = Vec::new();
+        let mut remaining = len;
+        let mut chunk = [0u8; 512];
+
+        while remaining > 0 {
+            let to_read = std::cmp::min(remaining, chunk.len());
+            match self.read_once(&mut chunk[..to_read]) {
+                Ok(0) => return Err(DataError::EndOfStream),
+                Ok(size) => {
+                    data.extend_from_slice(&chunk[..size]);
+                    remaining -= size;
+                }
+                Err(err) => return Err(DataError::IOError(err)),
+            }
+        }
+
+        Ok(data)
+    }
+
+    pub fn read_string_with_len(&mut self, len: usize) -> Result<String, DataError> {
+        let data = self.read_bytes_vec(len)?;
+        String::from_utf8(data).map_err(|_err| DataError::Utf8Error)
+    }
+
+    pub fn read_length_prefixed_string(&mut self) -> Result<String, DataError> {
+        let len = self.read_u32()? as usize;
+        self.read_string_with_len(len)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, DataError> {
+        self.get_next_byte()
+    }
+
+    pub fn read_i8(&mut self) -> Result<i8, DataError> {
+        Ok(self.read_u8()? as i8)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, DataError> {
+        let mut buf = [0u8; 2];
+        self.read_bytes(&mut buf)?;
+        Ok(match self.endianness {
+            Endianness::Little => u16::from_le_bytes(buf),
+            Endianness::Big => u16::from_be_bytes(buf),
+        })
+    }
+
+    pub fn read_i16(&mut self) -> Result<i16, DataError> {
+        Ok(self.read_u16()? as i16)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, DataError> {
+        let mut buf = [0u8; 4];
+        self.read_bytes(&mut buf)?;
+        Ok(match self.endianness {
+            Endianness::Little => u32::from_le_bytes(buf),
+            Endianness::Big => u32::from_be_bytes(buf),
+        })
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, DataError> {
+        Ok(self.read_u32()? as i32)
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, DataError> {
+        let mut buf = [0u8; 8];
+        self.read_bytes(&mut buf)?;
+        Ok(match self.endianness {
+            Endianness::Little => u64::from_le_bytes(buf),
+            Endianness::Big => u64::from_be_bytes(buf),
+        })
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, DataError> {
+        Ok(self.read_u64()? as i64)
+    }
+
+    pub fn read_f32(&mut self) -> Result<f32, DataError> {
+        Ok(f32::from_bits(self.read_u32()?))
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, DataError> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
+
+    pub fn read_varint_u64(&mut self) -> Result<u64, DataError> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        for _ in 0..10 {
+            let byte = self.read_u8()?;
+            let data_bits = (byte & 0x7f) as u64;
+
+            // On the 10th byte only bit 63 is left to fill, so any data bit above
+            // that would silently overflow the shift instead of being rejected.
+            if shift == 63 && data_bits > 1 {
+                return Err(DataError::VarintOverflow);
+            }
+
+            result |= data_bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+        }
+
+        Err(DataError::VarintOverflow)
+    }
+
+    pub fn read_varint_i64(&mut self) -> Result<i64, DataError> {
+        Ok(self.read_varint_u64()? as i64)
+    }
+
     fn load_data(&mut self) -> std::io::Result<usize> {
         self.data_pointer = 0;
         match self.reader.read(&mut self.buffer) {
             Ok(size) => {
                 self.data_available = size;
+                self.bytes_loaded += size as u64;
                 Ok(size)
             }
             Err(err) => Err(err),
@@ -90,13 +437,33 @@ impl DataProvider {
     }
 }
 
+impl std::io::Read for DataProvider {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.read_once(buf)
+    }
+}
+
+impl std::io::BufRead for DataProvider {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.data_pointer == self.data_available {
+            self.load_data()?;
+        }
+
+        Ok(&self.buffer[self.data_pointer..self.data_available])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.data_pointer = std::cmp::min(self.data_pointer + amt, self.data_available);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
     use hawktracer_parser_test_utilities::FakeDataReader;
 
     fn buffers_equal(b1: &[u8], b2: &[u8]) -> usize {
-        return b1.iter().zip(b2).map(|(a, b)| assert_eq!(a, b)).count();
+        b1.iter().zip(b2).map(|(a, b)| assert_eq!(a, b)).count()
     }
 
     #[test]
@@ -152,4 +519,492 @@ pub mod tests {
         let message = provider.read_string();
         assert!(message.is_err());
     }
+
+    #[test]
+    fn with_encoding_none_should_passthrough_bytes() {
+        use std::io::Read as _;
+
+        let mut provider = DataProvider::with_encoding(
+            Box::new(FakeDataReader::new(vec![1, 2, 3], false)),
+            Encoding::None,
+        )
+        .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        buffers_equal(&decoded, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn with_encoding_gzip_should_round_trip() {
+        use std::io::{Read as _, Write as _};
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello gzip").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut provider =
+            DataProvider::with_encoding(Box::new(std::io::Cursor::new(compressed)), Encoding::Gzip)
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, b"hello gzip");
+    }
+
+    #[test]
+    fn with_encoding_zlib_should_round_trip() {
+        use std::io::{Read as _, Write as _};
+
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello zlib").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut provider =
+            DataProvider::with_encoding(Box::new(std::io::Cursor::new(compressed)), Encoding::Zlib)
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, b"hello zlib");
+    }
+
+    #[test]
+    fn with_encoding_brotli_should_round_trip() {
+        use std::io::Read as _;
+
+        let plain = b"hello brotli".to_vec();
+        let mut compressed = Vec::new();
+        brotli::BrotliCompress(
+            &mut std::io::Cursor::new(plain.clone()),
+            &mut compressed,
+            &brotli::enc::BrotliEncoderParams::default(),
+        )
+        .unwrap();
+
+        let mut provider = DataProvider::with_encoding(
+            Box::new(std::io::Cursor::new(compressed)),
+            Encoding::Brotli,
+        )
+        .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+    }
+
+    #[test]
+    fn with_encoding_zstd_should_round_trip() {
+        use std::io::Read as _;
+
+        let plain = b"hello zstd".to_vec();
+        let compressed = zstd::stream::encode_all(std::io::Cursor::new(plain.clone()), 0).unwrap();
+
+        let mut provider =
+            DataProvider::with_encoding(Box::new(std::io::Cursor::new(compressed)), Encoding::Zstd)
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+    }
+
+    #[test]
+    fn with_sniffed_encoding_should_detect_gzip_magic() {
+        use std::io::{Read as _, Write as _};
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"sniffed gzip").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut provider =
+            DataProvider::with_sniffed_encoding(Box::new(std::io::Cursor::new(compressed)))
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, b"sniffed gzip");
+    }
+
+    #[test]
+    fn with_sniffed_encoding_should_detect_zstd_magic() {
+        use std::io::Read as _;
+
+        let plain = b"sniffed zstd".to_vec();
+        let compressed = zstd::stream::encode_all(std::io::Cursor::new(plain.clone()), 0).unwrap();
+
+        let mut provider =
+            DataProvider::with_sniffed_encoding(Box::new(std::io::Cursor::new(compressed)))
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+    }
+
+    #[test]
+    fn with_sniffed_encoding_should_fall_back_to_none_without_a_magic_match() {
+        use std::io::Read as _;
+
+        let mut provider =
+            DataProvider::with_sniffed_encoding(Box::new(FakeDataReader::new(vec![1, 2, 3], false)))
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        buffers_equal(&decoded, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn with_sniffed_encoding_should_handle_a_stream_shorter_than_the_magic_window() {
+        use std::io::Read as _;
+
+        let mut provider =
+            DataProvider::with_sniffed_encoding(Box::new(FakeDataReader::new(vec![1, 2], false)))
+                .unwrap();
+
+        let mut decoded = Vec::new();
+        provider.read_to_end(&mut decoded).unwrap();
+        buffers_equal(&decoded, &[1, 2]);
+    }
+
+    #[test]
+    fn read_bytes_vec_should_read_exactly_len_bytes() {
+        let mut provider =
+            DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2, 3, 4], false)));
+
+        let data = provider.read_bytes_vec(3).unwrap();
+        buffers_equal(&data, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn read_bytes_vec_should_bail_without_over_allocating_when_len_exceeds_the_stream() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2], false)));
+
+        assert_eq!(
+            provider.read_bytes_vec(1_000_000_000).unwrap_err(),
+            DataError::EndOfStream
+        );
+    }
+
+    #[test]
+    fn read_string_with_len_should_not_look_for_a_trailing_zero() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![65, 66, 67, 68],
+            false,
+        )));
+
+        let message = provider.read_string_with_len(4);
+        assert!(message.is_ok());
+        assert_eq!("ABCD", message.unwrap());
+    }
+
+    #[test]
+    fn read_string_with_len_should_fail_if_non_utf8_string() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![65, 220], false)));
+
+        let message = provider.read_string_with_len(2);
+        assert!(message.is_err());
+    }
+
+    #[test]
+    fn read_length_prefixed_string_should_read_the_u32_prefix_then_the_bytes() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![3, 0, 0, 0, 65, 66, 67],
+            false,
+        )));
+
+        let message = provider.read_length_prefixed_string();
+        assert!(message.is_ok());
+        assert_eq!("ABC", message.unwrap());
+    }
+
+    #[test]
+    fn position_should_track_consumed_bytes_not_buffered_ones() {
+        let mut provider =
+            DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2, 3, 4], false)));
+
+        assert_eq!(provider.position(), 0);
+
+        let mut buf = [0u8; 2];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        assert_eq!(provider.position(), 2);
+
+        provider.peek_byte().unwrap();
+        assert_eq!(provider.position(), 2);
+    }
+
+    #[test]
+    fn seek_should_rewind_a_seekable_source() {
+        let mut provider =
+            DataProvider::new_seekable(Box::new(std::io::Cursor::new(vec![1, 2, 3, 4])));
+
+        let mut buf = [0u8; 2];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[1, 2]);
+
+        assert_eq!(provider.seek(std::io::SeekFrom::Start(0)).unwrap(), 0);
+        assert_eq!(provider.position(), 0);
+
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[1, 2]);
+    }
+
+    #[test]
+    fn seek_current_should_account_for_buffered_but_unread_bytes() {
+        let mut provider =
+            DataProvider::new_seekable(Box::new(std::io::Cursor::new(vec![1, 2, 3, 4, 5, 6])));
+
+        let mut buf = [0u8; 2];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[1, 2]);
+        assert_eq!(provider.position(), 2);
+
+        assert_eq!(provider.seek(std::io::SeekFrom::Current(0)).unwrap(), 2);
+        assert_eq!(provider.position(), 2);
+
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[3, 4]);
+    }
+
+    #[test]
+    fn seek_current_should_support_a_positive_offset() {
+        let mut provider =
+            DataProvider::new_seekable(Box::new(std::io::Cursor::new(vec![1, 2, 3, 4, 5, 6])));
+
+        let mut buf = [0u8; 2];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+
+        assert_eq!(provider.seek(std::io::SeekFrom::Current(2)).unwrap(), 4);
+
+        let mut tail = [0u8; 2];
+        assert!(provider.read_bytes(&mut tail).is_ok());
+        buffers_equal(&tail, &[5, 6]);
+    }
+
+    #[test]
+    fn seek_should_fail_on_a_non_seekable_source() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2], false)));
+
+        assert_eq!(
+            provider.seek(std::io::SeekFrom::Start(0)).unwrap_err(),
+            DataError::NotSeekable
+        );
+    }
+
+    #[test]
+    fn read_to_end_should_work_through_the_std_read_impl() {
+        use std::io::Read as _;
+
+        let mut provider =
+            DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2, 3, 4], false)));
+        let mut collected = Vec::new();
+        assert!(provider.read_to_end(&mut collected).is_ok());
+
+        buffers_equal(&collected, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn fill_buf_and_consume_should_expose_the_internal_buffer() {
+        use std::io::BufRead as _;
+
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2], false)));
+
+        buffers_equal(provider.fill_buf().unwrap(), &[1, 2]);
+        provider.consume(1);
+
+        let mut buf = [0u8; 1];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[2]);
+    }
+
+    #[test]
+    fn peek_byte_should_not_advance_the_pointer() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2], false)));
+
+        assert_eq!(provider.peek_byte().unwrap(), 1);
+        assert_eq!(provider.peek_byte().unwrap(), 1);
+
+        let mut buf = [0u8; 2];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[1, 2]);
+    }
+
+    #[test]
+    fn peek_byte_should_fail_on_empty_stream() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![], false)));
+
+        assert_eq!(provider.peek_byte().unwrap_err(), DataError::EndOfStream);
+    }
+
+    #[test]
+    fn peek_bytes_should_not_advance_the_pointer() {
+        let mut provider =
+            DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2, 3, 4], false)));
+
+        buffers_equal(provider.peek_bytes(2).unwrap(), &[1, 2]);
+        buffers_equal(provider.peek_bytes(4).unwrap(), &[1, 2, 3, 4]);
+
+        let mut buf = [0u8; 4];
+        assert!(provider.read_bytes(&mut buf).is_ok());
+        buffers_equal(&buf, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn peek_bytes_should_fail_if_stream_is_shorter_than_requested() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![1, 2], false)));
+
+        assert_eq!(
+            provider.peek_bytes(3).unwrap_err(),
+            DataError::EndOfStream
+        );
+    }
+
+    #[test]
+    fn read_i8_should_preserve_negative_values() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![0xFF], false)));
+
+        assert_eq!(provider.read_i8().unwrap(), -1);
+    }
+
+    #[test]
+    fn read_i16_should_preserve_negative_values() {
+        let mut provider =
+            DataProvider::new(Box::new(FakeDataReader::new(vec![0xFF, 0xFF], false)));
+
+        assert_eq!(provider.read_i16().unwrap(), -1);
+    }
+
+    #[test]
+    fn read_i32_should_preserve_negative_values() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![0xFF, 0xFF, 0xFF, 0xFF],
+            false,
+        )));
+
+        assert_eq!(provider.read_i32().unwrap(), -1);
+    }
+
+    #[test]
+    fn read_i64_should_preserve_negative_values() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+            false,
+        )));
+
+        assert_eq!(provider.read_i64().unwrap(), -1);
+    }
+
+    #[test]
+    fn read_varint_i64_should_read_a_positive_value() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![42], false)));
+
+        assert_eq!(provider.read_varint_i64().unwrap(), 42);
+    }
+
+    #[test]
+    fn read_u16_should_default_to_little_endian() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![0x01, 0x02], false)));
+
+        assert_eq!(provider.read_u16().unwrap(), 0x0201);
+    }
+
+    #[test]
+    fn read_u16_should_honor_big_endian() {
+        let mut provider = DataProvider::with_endianness(
+            Box::new(FakeDataReader::new(vec![0x01, 0x02], false)),
+            Endianness::Big,
+        );
+
+        assert_eq!(provider.read_u16().unwrap(), 0x0102);
+    }
+
+    #[test]
+    fn read_u32_should_read_four_bytes() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![0x01, 0x02, 0x03, 0x04],
+            false,
+        )));
+
+        assert_eq!(provider.read_u32().unwrap(), 0x0403_0201);
+    }
+
+    #[test]
+    fn read_u64_should_read_eight_bytes() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![1, 0, 0, 0, 0, 0, 0, 0],
+            false,
+        )));
+
+        assert_eq!(provider.read_u64().unwrap(), 1);
+    }
+
+    #[test]
+    fn read_f32_should_decode_from_bits() {
+        let bits = 1.5f32.to_bits().to_le_bytes().to_vec();
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(bits, false)));
+
+        assert_eq!(provider.read_f32().unwrap(), 1.5f32);
+    }
+
+    #[test]
+    fn read_f64_should_decode_from_bits() {
+        let bits = 1.5f64.to_bits().to_le_bytes().to_vec();
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(bits, false)));
+
+        assert_eq!(provider.read_f64().unwrap(), 1.5f64);
+    }
+
+    #[test]
+    fn read_varint_u64_should_read_single_byte_value() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![42], false)));
+
+        assert_eq!(provider.read_varint_u64().unwrap(), 42);
+    }
+
+    #[test]
+    fn read_varint_u64_should_read_multi_byte_value() {
+        // 300 = 0b1_0010_1100 -> 0xAC 0x02
+        let mut provider =
+            DataProvider::new(Box::new(FakeDataReader::new(vec![0xAC, 0x02], false)));
+
+        assert_eq!(provider.read_varint_u64().unwrap(), 300);
+    }
+
+    #[test]
+    fn read_varint_u64_should_fail_on_overflow() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![0xFF; 10],
+            false,
+        )));
+
+        assert_eq!(
+            provider.read_varint_u64().unwrap_err(),
+            DataError::VarintOverflow
+        );
+    }
+
+    #[test]
+    fn read_varint_u64_should_fail_when_the_final_byte_overflows_bit_63() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02],
+            false,
+        )));
+
+        assert_eq!(
+            provider.read_varint_u64().unwrap_err(),
+            DataError::VarintOverflow
+        );
+    }
+
+    #[test]
+    fn read_varint_u64_should_fail_on_truncated_stream() {
+        let mut provider = DataProvider::new(Box::new(FakeDataReader::new(vec![0xFF], false)));
+
+        assert_eq!(
+            provider.read_varint_u64().unwrap_err(),
+            DataError::EndOfStream
+        );
+    }
 }